@@ -7,38 +7,640 @@
 //! - XFactor-botMax: Full features (GitHub, localhost, desktop)
 //! - XFactor-botMin: Restricted features (GitLab deployments)
 
-use std::sync::{Mutex, atomic::{AtomicBool, AtomicU32, Ordering}};
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU32, Ordering}};
 use std::process::{Command, Stdio};
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use shared_child::SharedChild;
 use tauri::{
     menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Emitter, Manager, Runtime,
+    tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
+    AppHandle, Emitter, EventTarget, Manager, PhysicalPosition, PhysicalSize, Runtime,
 };
+use tauri_plugin_dialog::DialogExt;
 use tauri_plugin_shell::ShellExt;
 use tauri_plugin_shell::process::CommandChild;
 
+mod telemetry;
+mod updater;
+
+/// The update found by the last `check_for_update` call, awaiting `install_update`.
+static PENDING_UPDATE: Mutex<Option<updater::PendingUpdate>> = Mutex::new(None);
+
+/// Whether closing the main window hides it to the tray instead of quitting
+/// the app outright, so trading sessions keep running in the background.
+/// Defaults to on; set `XFACTOR_HIDE_TO_TRAY=0` (or `false`/`off`) to restore
+/// close-quits-the-app behavior.
+static HIDE_TO_TRAY: AtomicBool = AtomicBool::new(true);
+
+fn hide_to_tray_enabled() -> bool {
+    HIDE_TO_TRAY.load(Ordering::SeqCst)
+}
+
+/// Labels of windows beyond "main" tagged with a caller-chosen role (e.g.
+/// "log-panel" for a detached log viewer), registered via
+/// `register_window_role` so `emit_to_role` can target them without
+/// broadcasting to every open window. Entries are dropped when their window
+/// is destroyed.
+#[derive(Default)]
+pub struct WindowRoles {
+    roles: Mutex<HashMap<String, String>>,
+}
+
+/// Tag `label` with `role` so a later `emit_to_role(..., role)` reaches it.
+#[tauri::command]
+fn register_window_role(state: tauri::State<'_, WindowRoles>, label: String, role: String) {
+    state.roles.lock().unwrap().insert(label, role);
+}
+
+/// Serialize `payload` once and deliver `event` only to windows whose label
+/// satisfies `predicate`, instead of `Emitter::emit`'s broadcast to every
+/// window. The basis for `emit_to_main`/`emit_to_role` below.
+fn emit_filtered<S: Serialize + Clone>(
+    app: &AppHandle,
+    event: &str,
+    payload: S,
+    predicate: impl Fn(&str) -> bool,
+) {
+    let _ = app.emit_filter(event, payload, |target| match target {
+        EventTarget::WebviewWindow { label } | EventTarget::Webview { label } | EventTarget::Window { label } => {
+            predicate(label)
+        }
+        _ => false,
+    });
+}
+
+/// Emit only to the main dashboard window.
+fn emit_to_main<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    emit_filtered(app, event, payload, |label| label == "main");
+}
+
+/// Emit only to windows registered under `role` via `register_window_role`.
+fn emit_to_role<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S, role: &str) {
+    let Some(state) = app.try_state::<WindowRoles>() else {
+        return;
+    };
+    let roles = state.roles.lock().unwrap();
+    emit_filtered(app, event, payload, |label| {
+        roles.get(label).map(String::as_str) == Some(role)
+    });
+}
+
+/// How long `graceful_kill_backend` waits for the detached process to exit
+/// after a termination signal before escalating to a forced kill.
+const GRACEFUL_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of backend log lines kept in the in-memory ring buffer.
+const MAX_LOG_LINES: usize = 5000;
+
+/// A single line of backend output, forwarded to the webview as `backend-log`.
+#[derive(Clone, Serialize)]
+struct LogLine {
+    level: &'static str,
+    text: String,
+    ts: u64,
+}
+
+fn current_ts_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Classify a line as error/warn/info, preferring an explicit `INFO`/`WARN`/
+/// `ERROR` prefix and otherwise trusting which stream it came from.
+fn classify_log_level(text: &str, is_stderr: bool) -> &'static str {
+    let trimmed = text.trim_start().trim_start_matches('[').to_uppercase();
+    if trimmed.starts_with("ERROR") {
+        "error"
+    } else if trimmed.starts_with("WARN") {
+        "warn"
+    } else if trimmed.starts_with("INFO") {
+        "info"
+    } else if is_stderr {
+        "error"
+    } else {
+        "info"
+    }
+}
+
+/// Push a backend log line into the ring buffer and forward it to windows
+/// that opened a log panel (role `"log-panel"`, see `register_window_role`),
+/// rather than broadcasting to every window.
+fn push_backend_log(app: &AppHandle, text: String, is_stderr: bool) {
+    let line = LogLine {
+        level: classify_log_level(&text, is_stderr),
+        text,
+        ts: current_ts_millis(),
+    };
+
+    if let Some(state) = app.try_state::<BackendState>() {
+        let mut logs = state.logs.lock().unwrap();
+        if logs.len() >= MAX_LOG_LINES {
+            logs.pop_front();
+        }
+        logs.push_back(line.clone());
+    }
+
+    emit_to_role(app, "backend-log", line, "log-panel");
+}
+
+/// Backend health endpoint polled by the supervisor task.
+const BACKEND_HEALTH_URL: &str = "http://127.0.0.1:9876/health";
+/// How often the supervisor polls the health endpoint.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(3);
+/// Consecutive failed checks before the backend is considered crashed.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+/// Initial delay before a restart attempt, doubled on each subsequent failure.
+const RESTART_BACKOFF_BASE: Duration = Duration::from_millis(500);
+/// Upper bound for the restart backoff delay.
+const RESTART_BACKOFF_CAP: Duration = Duration::from_secs(30);
+
+/// Initial delay before restarting a sidecar that exited unexpectedly,
+/// doubled on each consecutive restart attempt up to `RESTART_BACKOFF_CAP`.
+const SIDECAR_RESTART_BACKOFF_BASE: Duration = Duration::from_secs(1);
+/// How long after a crash-triggered relaunch the backend's health is checked
+/// again; if it is healthy at that point the shared restart counter is reset
+/// to zero.
+const RESTART_STABILITY_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// Backoff delay before the `attempt`th restart of a crashed backend:
+/// `SIDECAR_RESTART_BACKOFF_BASE` doubled per attempt, capped at `RESTART_BACKOFF_CAP`.
+fn sidecar_restart_backoff(attempt: u32) -> Duration {
+    let shift = attempt.saturating_sub(1).min(6);
+    (SIDECAR_RESTART_BACKOFF_BASE * (1u32 << shift)).min(RESTART_BACKOFF_CAP)
+}
+
+/// Name of the declarative bot-definitions file under `app_config_dir`.
+const BOTS_CONFIG_FILE: &str = "bots.json";
+
+/// A single bot definition as loaded from `bots.json`.
+#[derive(Clone, Serialize, Deserialize)]
+struct BotDefinition {
+    id: String,
+    account: String,
+    strategy: String,
+    symbols: Vec<String>,
+    enabled: bool,
+}
+
+/// The parsed set of bot definitions, reloaded whenever `bots.json` changes.
+#[derive(Default)]
+pub struct BotsState {
+    bots: Mutex<Vec<BotDefinition>>,
+}
+
+/// Resolve the path to the user's `bots.json` under `app_config_dir`.
+fn bots_config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve app config dir: {}", e))?;
+    Ok(dir.join(BOTS_CONFIG_FILE))
+}
+
+/// Load bot definitions from disk, starting with an empty list if the file
+/// doesn't exist yet or fails to parse.
+fn load_bots_config(path: &Path) -> Vec<BotDefinition> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            log::warn!("Failed to parse {}: {}", path.display(), e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Persist bot definitions to disk as pretty-printed JSON.
+fn save_bots_config(path: &Path, bots: &[BotDefinition]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(bots).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Build an HTTP client for UI-triggered backend control requests
+/// (bot start/stop/pause, kill switch), bounded to the same 2s timeout
+/// `spawn_backend_supervisor` uses for health checks - these commands must
+/// return promptly even when the backend is unreachable or hung, not hang
+/// the UI indefinitely.
+fn backend_control_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .unwrap_or_else(|e| {
+            log::error!("Failed to build backend control HTTP client: {}", e);
+            reqwest::Client::new()
+        })
+}
+
+/// Start, stop or pause every enabled bot by POSTing to the backend API,
+/// then reconcile `TradingState.active_bots` from the outcome.
+async fn run_bot_action(app: AppHandle, action: &'static str) {
+    let Some(bots_state) = app.try_state::<BotsState>() else {
+        return;
+    };
+    let enabled: Vec<BotDefinition> = {
+        let bots = bots_state.bots.lock().unwrap();
+        bots.iter().filter(|b| b.enabled).cloned().collect()
+    };
+
+    if enabled.is_empty() {
+        log::info!("No enabled bots configured, nothing to {}", action);
+        return;
+    }
+
+    let halted = app
+        .try_state::<TradingState>()
+        .map(|s| s.halted.load(Ordering::SeqCst))
+        .unwrap_or(false);
+    if action == "start" && halted {
+        log::warn!("Kill switch is engaged, refusing to start bots until the halt is cleared");
+        return;
+    }
+
+    let client = backend_control_client();
+    let mut succeeded = 0u32;
+    for bot in &enabled {
+        let url = format!("http://127.0.0.1:9876/bots/{}/{}", bot.id, action);
+        match client.post(&url).send().await {
+            Ok(response) if response.status().is_success() => {
+                log::info!("Bot '{}' {}", bot.id, action);
+                succeeded += 1;
+            }
+            Ok(response) => {
+                log::warn!("Bot '{}' {} failed: HTTP {}", bot.id, action, response.status());
+            }
+            Err(e) => {
+                log::warn!("Bot '{}' {} request failed: {}", bot.id, action, e);
+            }
+        }
+    }
+
+    if let Some(trading_state) = app.try_state::<TradingState>() {
+        match action {
+            "start" => trading_state.active_bots.store(succeeded, Ordering::SeqCst),
+            "stop" => trading_state.active_bots.store(0, Ordering::SeqCst),
+            _ => {}
+        }
+    }
+}
+
+/// Open a native file picker, replace the bot definitions with its contents,
+/// and persist them to `bots.json`.
+fn import_bots_config(app: &AppHandle) {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter("Bot Configuration", &["json"])
+        .blocking_pick_file()
+    else {
+        return;
+    };
+
+    let Some(path) = file_path.as_path() else {
+        log::warn!("Import cancelled: picked file has no filesystem path");
+        return;
+    };
+
+    let bots = load_bots_config(path);
+    let Ok(dest) = bots_config_path(app) else {
+        return;
+    };
+    if let Err(e) = save_bots_config(&dest, &bots) {
+        log::error!("Failed to import bot configuration: {}", e);
+        return;
+    }
+
+    if let Some(state) = app.try_state::<BotsState>() {
+        *state.bots.lock().unwrap() = bots.clone();
+    }
+    let _ = app.emit("bots://changed", bots);
+}
+
+/// Open a native save dialog and write the current bot definitions to it.
+fn export_bots_config(app: &AppHandle) {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter("Bot Configuration", &["json"])
+        .set_file_name(BOTS_CONFIG_FILE)
+        .blocking_save_file()
+    else {
+        return;
+    };
+
+    let Some(path) = file_path.as_path() else {
+        log::warn!("Export cancelled: picked destination has no filesystem path");
+        return;
+    };
+
+    let bots = app
+        .try_state::<BotsState>()
+        .map(|state| state.bots.lock().unwrap().clone())
+        .unwrap_or_default();
+
+    if let Err(e) = save_bots_config(path, &bots) {
+        log::error!("Failed to export bot configuration: {}", e);
+    }
+}
+
+/// How long to hold off after a filesystem event before re-reading a
+/// config file, so editors that write-then-rename don't trigger a reload
+/// per intermediate write.
+const CONFIG_RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Re-parse `bots.json` and broadcast the result, retrying once after the
+/// debounce window in case the file was caught mid-write.
+fn reload_bots_config(app: &AppHandle, path: &Path) {
+    fn try_parse(path: &Path) -> Result<Vec<BotDefinition>, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&contents).map_err(|e| e.to_string())
+    }
+
+    let parsed = try_parse(path).or_else(|_| {
+        std::thread::sleep(CONFIG_RELOAD_DEBOUNCE);
+        try_parse(path)
+    });
+
+    match parsed {
+        Ok(bots) => {
+            if let Some(state) = app.try_state::<BotsState>() {
+                *state.bots.lock().unwrap() = bots.clone();
+            }
+            log::info!("Reloaded {} ({} bot definition(s))", path.display(), bots.len());
+            let _ = app.emit("config://reloaded", bots);
+        }
+        Err(e) => {
+            log::warn!("Failed to reload {}: {}", path.display(), e);
+            let _ = app.emit("config://error", e);
+        }
+    }
+}
+
+/// Watch `app_config_dir` for edits to `bots.json` and hot-reload it without
+/// requiring an app restart. Bursts of write/rename events (common with
+/// editors that save via a temp file + rename) are coalesced by waiting out
+/// `CONFIG_RELOAD_DEBOUNCE` and draining anything else that arrives in that
+/// window before reloading once. Exits once `is_shutting_down` is set.
+fn spawn_config_watcher(app: AppHandle) {
+    let Ok(config_dir) = app.path().app_config_dir() else {
+        log::warn!("Cannot resolve app config dir, config watcher disabled");
+        return;
+    };
+    if let Err(e) = std::fs::create_dir_all(&config_dir) {
+        log::warn!("Failed to create {}: {}", config_dir.display(), e);
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let (tx, rx) = std::sync::mpsc::channel::<notify::Result<Event>>();
+        let mut watcher = match notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        }) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                log::error!("Failed to start config watcher: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = watcher.watch(&config_dir, RecursiveMode::NonRecursive) {
+            log::error!("Failed to watch {}: {}", config_dir.display(), e);
+            return;
+        }
+
+        log::info!("Watching {} for configuration changes", config_dir.display());
+
+        loop {
+            let shutting_down = app
+                .try_state::<BackendState>()
+                .map(|s| s.is_shutting_down.load(Ordering::SeqCst))
+                .unwrap_or(true);
+            if shutting_down {
+                log::info!("Config watcher exiting: shutdown in progress");
+                break;
+            }
+
+            match rx.recv_timeout(Duration::from_millis(500)) {
+                Ok(Ok(event)) => {
+                    if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                        continue;
+                    }
+
+                    // Debounce bursts, then drain whatever else piled up,
+                    // keeping the paths those events touched.
+                    std::thread::sleep(CONFIG_RELOAD_DEBOUNCE);
+                    let mut paths = event.paths;
+                    while let Ok(res) = rx.try_recv() {
+                        if let Ok(ev) = res {
+                            if matches!(ev.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                                paths.extend(ev.paths);
+                            }
+                        }
+                    }
+
+                    let touched_bots = paths
+                        .iter()
+                        .any(|p| p.file_name().and_then(|n| n.to_str()) == Some(BOTS_CONFIG_FILE));
+                    if touched_bots {
+                        reload_bots_config(&app, &config_dir.join(BOTS_CONFIG_FILE));
+                    }
+                }
+                Ok(Err(e)) => log::warn!("Config watcher error: {}", e),
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+}
+
+/// Directory (under `app_data_dir`) holding one persisted geometry file per
+/// window, named `<label>.json` - so closing a secondary window (a detached
+/// chart, settings, etc.) can't clobber the main window's saved geometry.
+const WINDOW_STATE_DIR: &str = "window-state";
+
+/// Saved position/size/maximized/fullscreen state for a single window,
+/// persisted so it reopens where it was left instead of re-centering.
+#[derive(Clone, Serialize, Deserialize)]
+struct WindowGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+    fullscreen: bool,
+}
+
+/// Resolve the path to `label`'s persisted geometry file.
+fn window_state_path(app: &AppHandle, label: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    Ok(dir.join(WINDOW_STATE_DIR).join(format!("{}.json", label)))
+}
+
+/// Capture `window`'s current geometry and persist it, best-effort.
+///
+/// Position/size are read via `outer_position`/`outer_size` rather than
+/// derived from the maximized/fullscreen state, since those reflect the
+/// window's restored geometry on most platforms even while maximized;
+/// `maximized`/`fullscreen` are tracked as separate flags instead.
+fn save_window_geometry<R: Runtime>(window: &tauri::Window<R>) {
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.outer_size()) else {
+        return;
+    };
+
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized: window.is_maximized().unwrap_or(false),
+        fullscreen: window.is_fullscreen().unwrap_or(false),
+    };
+
+    let Ok(path) = window_state_path(&window.app_handle(), window.label()) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            log::warn!("Failed to create {}: {}", parent.display(), e);
+            return;
+        }
+    }
+    match serde_json::to_string_pretty(&geometry) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("Failed to save window geometry to {}: {}", path.display(), e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize window geometry: {}", e),
+    }
+}
+
+/// Restore the window's last-known geometry, if any was saved.
+///
+/// Clamps the saved position onto the nearest visible monitor (falling back
+/// to centering on the primary monitor) so a changed monitor layout - an
+/// external display unplugged since the last run - can't leave the window
+/// off-screen.
+fn restore_window_geometry<R: Runtime>(window: &tauri::Window<R>) {
+    let Ok(path) = window_state_path(&window.app_handle(), window.label()) else {
+        return;
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    let geometry: WindowGeometry = match serde_json::from_str(&contents) {
+        Ok(geometry) => geometry,
+        Err(e) => {
+            log::warn!("Ignoring malformed {}: {}", path.display(), e);
+            return;
+        }
+    };
+
+    let monitors = window.available_monitors().unwrap_or_default();
+    let on_a_monitor = monitors.iter().any(|m| {
+        let pos = m.position();
+        let size = m.size();
+        geometry.x >= pos.x
+            && geometry.y >= pos.y
+            && geometry.x < pos.x + size.width as i32
+            && geometry.y < pos.y + size.height as i32
+    });
+
+    // The saved position clamped into each monitor's bounds; the closest
+    // of these is where an off-screen window is moved to.
+    let clamp_into = |m: &tauri::Monitor| {
+        let (pos, size) = (m.position(), m.size());
+        let max_x = pos.x + (size.width as i32 - geometry.width as i32).max(0);
+        let max_y = pos.y + (size.height as i32 - geometry.height as i32).max(0);
+        (geometry.x.clamp(pos.x, max_x), geometry.y.clamp(pos.y, max_y))
+    };
+    let nearest = monitors.iter().map(clamp_into).min_by_key(|&(x, y)| {
+        (x as i64 - geometry.x as i64).abs() + (y as i64 - geometry.y as i64).abs()
+    });
+
+    if on_a_monitor {
+        let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+    } else if let Some((x, y)) = nearest {
+        log::info!("Saved window position is off-screen, clamping it onto the nearest monitor");
+        let _ = window.set_position(PhysicalPosition::new(x, y));
+    } else if let Ok(Some(primary)) = window.primary_monitor() {
+        log::info!("Saved window position is off-screen, centering on the primary monitor instead");
+        let pos = primary.position();
+        let size = primary.size();
+        let x = pos.x + (size.width as i32 - geometry.width as i32) / 2;
+        let y = pos.y + (size.height as i32 - geometry.height as i32) / 2;
+        let _ = window.set_position(PhysicalPosition::new(x, y));
+    }
+
+    let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+    // Respect the `toggle-fullscreen` menu item's own state management by
+    // only forcing fullscreen on, never off - a saved `false` just leaves
+    // the window at whatever the normal restore above produced.
+    if geometry.fullscreen {
+        let _ = window.set_fullscreen(true);
+    }
+}
+
 /// Trading state shared across the app
 #[derive(Default)]
 pub struct TradingState {
-    pub is_trading: bool,
-    pub connected_accounts: u32,
-    pub active_bots: u32,
+    pub is_trading: AtomicBool,
+    pub connected_accounts: AtomicU32,
+    pub active_bots: AtomicU32,
+    /// Set by the kill switch; blocks new start requests until explicitly cleared.
+    pub halted: AtomicBool,
 }
 
 /// Backend process state with cleanup tracking
 pub struct BackendState {
+    /// The sidecar child, when the backend was launched via `tauri_plugin_shell`.
     pub child: Mutex<Option<CommandChild>>,
-    pub backend_pid: AtomicU32,
+    /// The detached child, when the backend was launched as a plain OS process.
+    /// `SharedChild` gives us a `kill()`/`wait()` pair that works the same way
+    /// on every platform, so shutdown no longer needs to shell out to
+    /// `pgrep`/`lsof`/`kill`/`taskkill`/`netstat`.
+    pub detached_child: Mutex<Option<Arc<SharedChild>>>,
     pub is_shutting_down: AtomicBool,
+    /// Set by `stop_backend`/`force_cleanup` so the exit that follows is
+    /// recognized as user-initiated and doesn't trigger an auto-restart.
+    pub deliberately_stopped: AtomicBool,
+    /// Number of consecutive failed health checks since the last success.
+    pub consecutive_failures: AtomicU32,
+    /// Last-known health state, updated by the supervisor task.
+    pub is_healthy: AtomicBool,
+    /// Restart attempts since the backoff was last reset, used by
+    /// `sidecar_restart_backoff` to compute the next restart delay.
+    pub restart_count: AtomicU32,
+    /// Bounded tail of recent backend stdout/stderr lines, capped at `MAX_LOG_LINES`.
+    pub logs: Mutex<VecDeque<LogLine>>,
 }
 
 impl Default for BackendState {
     fn default() -> Self {
         Self {
             child: Mutex::new(None),
-            backend_pid: AtomicU32::new(0),
+            detached_child: Mutex::new(None),
             is_shutting_down: AtomicBool::new(false),
+            deliberately_stopped: AtomicBool::new(false),
+            consecutive_failures: AtomicU32::new(0),
+            is_healthy: AtomicBool::new(false),
+            restart_count: AtomicU32::new(0),
+            logs: Mutex::new(VecDeque::new()),
         }
     }
 }
@@ -64,8 +666,13 @@ fn is_backend_running() -> bool {
     }
 }
 
-/// Kill any zombie xfactor-backend processes
-/// NOTE: This is ONLY called during shutdown/cleanup, NOT on startup
+/// Kill any zombie xfactor-backend processes.
+///
+/// This is the old shell-out-based cleanup path (`pgrep`/`lsof`/`kill`/
+/// `taskkill`/`netstat`). `graceful_kill_backend` no longer calls it during
+/// normal shutdown now that `BackendState` tracks its children directly via
+/// `SharedChild`/`CommandChild`; it's retained only as an explicit
+/// `force_cleanup` last resort for processes we've lost track of.
 fn kill_zombie_backends() {
     log::info!("Cleaning up backend processes...");
     
@@ -131,12 +738,51 @@ fn kill_zombie_backends() {
     }
 }
 
-/// Graceful shutdown - send SIGTERM first, then SIGKILL after timeout
-fn graceful_kill_backend(state: &BackendState) {
-    // Mark as shutting down to prevent restart attempts
-    state.is_shutting_down.store(true, Ordering::SeqCst);
-    
-    // First, try to gracefully stop the tracked child process
+/// Send a termination signal to a detached child, then wait up to
+/// `GRACEFUL_STOP_TIMEOUT` before escalating to a forced kill.
+///
+/// Unlike the old PID-string-parsing shutdown, this uses `SharedChild`
+/// directly: no `kill`/`taskkill` subprocess required to observe exit.
+fn stop_detached_child(child: &Arc<SharedChild>) {
+    #[cfg(unix)]
+    {
+        log::info!("Sending SIGTERM to backend (pid {})", child.id());
+        unsafe {
+            libc::kill(child.id() as i32, libc::SIGTERM);
+        }
+    }
+    #[cfg(windows)]
+    {
+        // SharedChild has no graceful-stop primitive on Windows, so the
+        // escalation below (kill() + wait()) is the only path there.
+        log::info!("Stopping backend (pid {})", child.id());
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let waiter = Arc::clone(child);
+    std::thread::spawn(move || {
+        let _ = waiter.wait();
+        let _ = tx.send(());
+    });
+
+    if rx.recv_timeout(GRACEFUL_STOP_TIMEOUT).is_err() {
+        log::warn!("Backend did not exit within {:?}, sending SIGKILL", GRACEFUL_STOP_TIMEOUT);
+        let _ = child.kill();
+        let _ = child.wait();
+    } else {
+        log::info!("Backend exited cleanly");
+    }
+}
+
+/// Graceful shutdown - send a termination signal first, then force-kill after a timeout.
+/// Kill whatever sidecar/detached child is currently tracked. This only
+/// tears down the process - it does not touch `is_shutting_down`. Callers
+/// that mean "the app is exiting, never restart" must set that flag
+/// themselves first; callers that mean "stop the backend, the app keeps
+/// running" (e.g. `stop_backend`) should leave it alone so the health
+/// supervisor, config watcher, and tray status updater keep running.
+pub(crate) fn graceful_kill_backend(state: &BackendState) {
+    // Sidecar path: CommandChild exposes its own kill()
     {
         let mut guard = state.child.lock().unwrap();
         if let Some(child) = guard.take() {
@@ -144,42 +790,27 @@ fn graceful_kill_backend(state: &BackendState) {
             let _ = child.kill();
         }
     }
-    
-    // Also kill by stored PID
-    let pid = state.backend_pid.load(Ordering::SeqCst);
-    if pid > 0 {
-        #[cfg(unix)]
-        {
-            log::info!("Sending SIGTERM to backend PID: {}", pid);
-            let _ = Command::new("kill")
-                .args(["-15", &pid.to_string()])
-                .output();
-            
-            // Give it a moment to shutdown gracefully
-            std::thread::sleep(Duration::from_millis(500));
-            
-            // Force kill if still running
-            log::info!("Sending SIGKILL to backend PID: {}", pid);
-            let _ = Command::new("kill")
-                .args(["-9", &pid.to_string()])
-                .output();
-        }
-        
-        #[cfg(windows)]
-        {
-            log::info!("Force killing backend PID: {}", pid);
-            let _ = Command::new("taskkill")
-                .args(["/F", "/PID", &pid.to_string()])
-                .output();
-        }
+
+    // Detached path: deterministic SIGTERM -> wait -> SIGKILL via SharedChild
+    let detached = state.detached_child.lock().unwrap().take();
+    if let Some(child) = detached {
+        stop_detached_child(&child);
     }
-    
-    // Final cleanup - kill any remaining zombie processes
-    kill_zombie_backends();
-    
+
     log::info!("Backend cleanup completed");
 }
 
+/// Tear down the tracked backend so the caller can immediately relaunch it.
+///
+/// Used by the health supervisor when the backend is still running but no
+/// longer answering health checks: the stale process is torn down first so
+/// the relaunch isn't left competing with it for port 9876. Just an alias
+/// for `graceful_kill_backend` - kept as a separate name since the two call
+/// sites mean different things even though the teardown itself is identical.
+fn teardown_for_restart(state: &BackendState) {
+    graceful_kill_backend(state);
+}
+
 /// Start the Python backend server
 #[tauri::command]
 async fn start_backend(app: tauri::AppHandle, state: tauri::State<'_, BackendState>) -> Result<String, String> {
@@ -208,15 +839,20 @@ async fn start_backend(app: tauri::AppHandle, state: tauri::State<'_, BackendSta
         .sidecar("xfactor-backend")
         .map_err(|e| format!("Failed to create sidecar command: {}", e))?;
     
-    let (mut _rx, child) = sidecar.spawn()
+    let (rx, child) = sidecar.spawn()
         .map_err(|e| format!("Failed to spawn backend: {}", e))?;
-    
+
     // Store the child process
     {
         let mut guard = state.child.lock().unwrap();
         *guard = Some(child);
     }
-    
+
+    // A user-requested start ends any earlier user-requested stop, so a
+    // later unexpected exit is free to trigger an auto-restart again.
+    state.deliberately_stopped.store(false, Ordering::SeqCst);
+    spawn_sidecar_event_pump(app.clone(), rx);
+
     log::info!("Backend sidecar started");
     Ok("Backend started".to_string())
 }
@@ -225,6 +861,7 @@ async fn start_backend(app: tauri::AppHandle, state: tauri::State<'_, BackendSta
 #[tauri::command]
 async fn stop_backend(state: tauri::State<'_, BackendState>) -> Result<String, String> {
     log::info!("Stop backend requested");
+    state.deliberately_stopped.store(true, Ordering::SeqCst);
     graceful_kill_backend(&state);
     Ok("Backend stopped and cleaned up".to_string())
 }
@@ -233,6 +870,7 @@ async fn stop_backend(state: tauri::State<'_, BackendState>) -> Result<String, S
 #[tauri::command]
 async fn force_cleanup(state: tauri::State<'_, BackendState>) -> Result<String, String> {
     log::warn!("Force cleanup requested - killing all backend processes");
+    state.deliberately_stopped.store(true, Ordering::SeqCst);
     state.is_shutting_down.store(true, Ordering::SeqCst);
     graceful_kill_backend(&state);
     kill_zombie_backends();
@@ -249,12 +887,475 @@ fn get_system_info() -> serde_json::Value {
     })
 }
 
+/// Emergency stop: flatten all positions and halt trading across every
+/// connected account, without touching the backend process itself.
+///
+/// This is deliberately idempotent and best-effort - the halt flag is set
+/// first so new start requests are blocked immediately, and a failed HTTP
+/// call to the backend is logged rather than returned as an error, since
+/// trading must stop from the UI's point of view even if the backend is
+/// having trouble. Stays set until `clear_halt` is called explicitly. The
+/// confirmation event is routed to the main window only, via
+/// `emit_to_main`, so detached chart/settings windows aren't interrupted.
+#[tauri::command]
+async fn kill_switch(app: AppHandle, trading_state: tauri::State<'_, TradingState>) -> Result<(), String> {
+    log::warn!("KILL SWITCH activated - flattening all positions and halting trading");
+    trading_state.halted.store(true, Ordering::SeqCst);
+
+    let client = backend_control_client();
+    for path in ["/flatten-all", "/halt"] {
+        let url = format!("http://127.0.0.1:9876{}", path);
+        match client.post(&url).send().await {
+            Ok(response) if !response.status().is_success() => {
+                log::error!("Kill switch request to {} failed: HTTP {}", path, response.status());
+            }
+            Ok(_) => {}
+            Err(e) => log::error!("Kill switch request to {} failed: {}", path, e),
+        }
+    }
+
+    emit_to_main(&app, "trading://halted", ());
+    Ok(())
+}
+
+/// Clear a previously-triggered kill switch so trading can resume.
+#[tauri::command]
+fn clear_halt(trading_state: tauri::State<'_, TradingState>) -> Result<(), String> {
+    trading_state.halted.store(false, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Get the current trading state, including whether the kill switch is engaged.
+#[tauri::command]
+fn get_trading_state(trading_state: tauri::State<'_, TradingState>) -> serde_json::Value {
+    serde_json::json!({
+        "is_trading": trading_state.is_trading.load(Ordering::SeqCst),
+        "connected_accounts": trading_state.connected_accounts.load(Ordering::SeqCst),
+        "active_bots": trading_state.active_bots.load(Ordering::SeqCst),
+        "halted": trading_state.halted.load(Ordering::SeqCst),
+    })
+}
+
 /// Check if backend is healthy
+///
+/// Returns the last-known state as tracked by the health supervisor task,
+/// not a fresh probe - the supervisor polls `BACKEND_HEALTH_URL` on its own
+/// cadence so this command stays cheap to call from the UI.
 #[tauri::command]
-async fn check_backend_health() -> Result<bool, String> {
-    // The frontend will handle health checks via its own HTTP client
-    // This is just a placeholder that returns true
-    Ok(true)
+async fn check_backend_health(state: tauri::State<'_, BackendState>) -> Result<bool, String> {
+    Ok(state.is_healthy.load(Ordering::SeqCst))
+}
+
+/// Issue a single HTTP health probe against the backend.
+async fn probe_backend_health(client: &reqwest::Client) -> bool {
+    match client.get(BACKEND_HEALTH_URL).send().await {
+        Ok(response) => response.status().is_success(),
+        Err(_) => false,
+    }
+}
+
+/// Locate and launch the backend, reusing an already-running instance if one is found.
+///
+/// This is the same spawn logic used on startup and is re-invoked by the
+/// health supervisor whenever the backend is judged to have crashed.
+async fn launch_backend(handle: AppHandle) {
+    log::info!("Starting backend...");
+
+    // A (re)launch means the backend is no longer in a user-requested-stop
+    // state, so a later unexpected exit is free to trigger an auto-restart.
+    if let Some(state) = handle.try_state::<BackendState>() {
+        state.deliberately_stopped.store(false, Ordering::SeqCst);
+    }
+
+    // Give the app a moment to initialize
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    // Collect all possible backend locations
+    let mut candidates: Vec<std::path::PathBuf> = Vec::new();
+
+    // Binary names to try (in order of preference)
+    let binary_names = [
+        "xfactor-backend",
+        "xfactor-backend-x86_64-apple-darwin",
+        "xfactor-backend-aarch64-apple-darwin",
+        "xfactor-backend-x86_64-unknown-linux-gnu",
+        "xfactor-backend-x86_64-pc-windows-msvc.exe",
+    ];
+
+    // Get various directories to search
+    if let Ok(resource_dir) = handle.path().resource_dir() {
+        log::info!("Resource dir: {:?}", resource_dir);
+
+        // MacOS folder (where externalBin places binaries in .app bundle)
+        if let Some(parent) = resource_dir.parent() {
+            let macos_dir = parent.join("MacOS");
+            log::info!("MacOS dir: {:?}", macos_dir);
+            for name in &binary_names {
+                candidates.push(macos_dir.join(name));
+            }
+        }
+
+        // Resources folder itself
+        for name in &binary_names {
+            candidates.push(resource_dir.join(name));
+        }
+
+        // binaries subfolder in Resources
+        let binaries_dir = resource_dir.join("binaries");
+        for name in &binary_names {
+            candidates.push(binaries_dir.join(name));
+        }
+    }
+
+    // Also check data folder (for development or manual placement)
+    if let Ok(app_data_dir) = handle.path().app_data_dir() {
+        log::info!("App data dir: {:?}", app_data_dir);
+        for name in &binary_names {
+            candidates.push(app_data_dir.join(name));
+        }
+    }
+
+    // Check current executable's directory
+    if let Ok(exe_path) = std::env::current_exe() {
+        if let Some(exe_dir) = exe_path.parent() {
+            log::info!("Executable dir: {:?}", exe_dir);
+            for name in &binary_names {
+                candidates.push(exe_dir.join(name));
+            }
+            // Also check binaries subfolder next to exe
+            let binaries_dir = exe_dir.join("binaries");
+            for name in &binary_names {
+                candidates.push(binaries_dir.join(name));
+            }
+        }
+    }
+
+    // Log all candidates for debugging
+    log::info!("Searching for backend in {} locations...", candidates.len());
+
+    // Find the first existing backend binary
+    let backend_path = candidates.into_iter().find(|p| {
+        let exists = p.exists();
+        if exists {
+            log::info!("FOUND backend at: {:?}", p);
+        }
+        exists
+    });
+
+    // FIRST: Check if backend is already running
+    // DON'T kill existing backends - just reuse them
+    if is_backend_running() {
+        log::info!("Backend is already running on port 9876 - reusing existing instance");
+        // Don't start a new one, just use the existing
+    } else if let Some(backend) = backend_path {
+        log::info!("No backend running, starting new instance at: {:?}", backend);
+
+        // Start the backend as a detached process (not a child of frontend)
+        // This prevents the backend from being killed when frontend closes unexpectedly.
+        // Stdio is piped (rather than null) so its output can be streamed to the UI.
+        #[cfg(unix)]
+        let spawned = {
+            // Use setsid on Unix to detach from parent process group
+            let mut cmd = Command::new("setsid");
+            cmd.arg(&backend).stdout(Stdio::piped()).stderr(Stdio::piped());
+            cmd.spawn().or_else(|_| {
+                // setsid might not be available, try without it
+                let mut cmd = Command::new(&backend);
+                cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+                cmd.spawn()
+            })
+        };
+
+        #[cfg(windows)]
+        let spawned = {
+            // Windows: Use CREATE_NEW_PROCESS_GROUP to detach
+            use std::os::windows::process::CommandExt;
+            const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+            const DETACHED_PROCESS: u32 = 0x00000008;
+
+            let mut cmd = Command::new(&backend);
+            cmd.creation_flags(CREATE_NEW_PROCESS_GROUP | DETACHED_PROCESS)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            cmd.spawn()
+        };
+
+        match spawned {
+            Ok(mut raw_child) => {
+                let stdout = raw_child.stdout.take();
+                let stderr = raw_child.stderr.take();
+
+                match SharedChild::new(raw_child) {
+                    Ok(child) => {
+                        let child = Arc::new(child);
+                        log::info!("Backend started as detached process (PID: {})", child.id());
+
+                        if let Some(stdout) = stdout {
+                            spawn_log_reader(handle.clone(), stdout, false);
+                        }
+                        if let Some(stderr) = stderr {
+                            spawn_log_reader(handle.clone(), stderr, true);
+                        }
+
+                        if let Some(state) = handle.try_state::<BackendState>() {
+                            let mut guard = state.detached_child.lock().unwrap();
+                            *guard = Some(Arc::clone(&child));
+                        }
+
+                        spawn_exit_watcher(handle.clone(), child);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to track spawned backend: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to spawn backend: {}", e);
+            }
+        }
+    } else {
+        // Try the sidecar mechanism as fallback (for dev mode)
+        match handle.shell().sidecar("xfactor-backend") {
+            Ok(sidecar) => {
+                match sidecar.spawn() {
+                    Ok((rx, child)) => {
+                        log::info!("Backend sidecar started successfully");
+
+                        if let Some(state) = handle.try_state::<BackendState>() {
+                            let mut guard = state.child.lock().unwrap();
+                            *guard = Some(child);
+                        }
+
+                        spawn_sidecar_event_pump(handle.clone(), rx);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to spawn backend sidecar: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                log::warn!("Backend not found (dev mode?): {}", e);
+            }
+        }
+    }
+}
+
+/// Pump a sidecar's event stream: forward stdout/stderr to `push_backend_log`
+/// and, on an unexpected `Terminated`, restart it after the delay given by
+/// `sidecar_restart_backoff`. Shared by the sidecar fallback above and the `start_backend`
+/// command, so a tracked sidecar's output and crashes are handled the same
+/// way no matter which path spawned it.
+fn spawn_sidecar_event_pump(
+    handle: AppHandle,
+    mut rx: tokio::sync::mpsc::Receiver<tauri_plugin_shell::process::CommandEvent>,
+) {
+    tauri::async_runtime::spawn(async move {
+        use tauri_plugin_shell::process::CommandEvent;
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => {
+                    push_backend_log(&handle, String::from_utf8_lossy(&line).into_owned(), false);
+                }
+                CommandEvent::Stderr(line) => {
+                    push_backend_log(&handle, String::from_utf8_lossy(&line).into_owned(), true);
+                }
+                CommandEvent::Terminated(status) => {
+                    log::info!("[Backend] Process terminated: {:?}", status);
+
+                    let Some(state) = handle.try_state::<BackendState>() else {
+                        break;
+                    };
+
+                    let user_stopped = state.deliberately_stopped.load(Ordering::SeqCst);
+                    let shutting_down = state.is_shutting_down.load(Ordering::SeqCst);
+                    let clean_exit = status.code == Some(0);
+                    let should_restart = !user_stopped && !shutting_down;
+
+                    if should_restart && !clean_exit {
+                        let tail = state
+                            .logs
+                            .lock()
+                            .unwrap()
+                            .iter()
+                            .rev()
+                            .take(20)
+                            .map(|l| l.text.clone())
+                            .collect::<Vec<_>>();
+                        telemetry::capture_backend_crash(status.code, &tail);
+                    }
+
+                    if !should_restart {
+                        break;
+                    }
+
+                    state.is_healthy.store(false, Ordering::SeqCst);
+                    let attempt = state.restart_count.fetch_add(1, Ordering::SeqCst) + 1;
+                    let delay = sidecar_restart_backoff(attempt);
+                    log::warn!(
+                        "Backend sidecar exited unexpectedly, restarting in {:?} (attempt {})",
+                        delay,
+                        attempt
+                    );
+                    let _ = handle.emit("backend-restarting", ());
+
+                    let handle = handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        launch_backend(handle.clone()).await;
+
+                        tokio::time::sleep(RESTART_STABILITY_THRESHOLD).await;
+                        if let Some(state) = handle.try_state::<BackendState>() {
+                            if state.is_healthy.load(Ordering::SeqCst) {
+                                state.restart_count.store(0, Ordering::SeqCst);
+                            }
+                        }
+                        let _ = handle.emit("backend-restarted", ());
+                    });
+                    break;
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+/// Read lines from a piped stdout/stderr handle on a background thread and
+/// forward each one to `push_backend_log`.
+fn spawn_log_reader<R: std::io::Read + Send + 'static>(handle: AppHandle, reader: R, is_stderr: bool) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            match line {
+                Ok(text) => push_backend_log(&handle, text, is_stderr),
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+/// Watch a detached child on a background thread and react once it exits.
+///
+/// This is what lets the health supervisor notice a crash immediately
+/// instead of waiting for the next failed health probe: an unexpected exit
+/// (shutdown not in progress) marks the backend unhealthy right away.
+fn spawn_exit_watcher(handle: AppHandle, child: Arc<SharedChild>) {
+    std::thread::spawn(move || {
+        let status = child.wait();
+        log::info!("Detached backend process exited: {:?}", status);
+
+        let Some(state) = handle.try_state::<BackendState>() else {
+            return;
+        };
+        let user_stopped = state.deliberately_stopped.load(Ordering::SeqCst);
+        let shutting_down = state.is_shutting_down.load(Ordering::SeqCst);
+        if user_stopped || shutting_down {
+            return;
+        }
+
+        state.is_healthy.store(false, Ordering::SeqCst);
+        let _ = handle.emit("backend://down", ());
+
+        let exit_code = status.ok().and_then(|s| s.code());
+        let clean_exit = exit_code == Some(0);
+        if !clean_exit {
+            let tail = state
+                .logs
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .take(20)
+                .map(|l| l.text.clone())
+                .collect::<Vec<_>>();
+            telemetry::capture_backend_crash(exit_code, &tail);
+        }
+    });
+}
+
+/// Spawn the supervisor task that owns the backend's lifecycle for the lifetime of the app.
+///
+/// Polls `BACKEND_HEALTH_URL` on `HEALTH_CHECK_INTERVAL`, tracks consecutive
+/// failures in `BackendState`, and tears down and relaunches the backend
+/// with exponential backoff once it's judged unresponsive. Emits
+/// `backend://healthy`, `backend://down` and `backend://restarting` for the
+/// dashboard, plus `backend-restarting`/`backend-restarted` shared with the
+/// sidecar's own crash-triggered restarts. Skips restarting (but keeps
+/// polling) while `deliberately_stopped` is set, and exits cleanly once
+/// `is_shutting_down` is set.
+fn spawn_backend_supervisor(handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let client = match reqwest::Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                log::error!("Failed to build health-check client, supervisor disabled: {}", e);
+                return;
+            }
+        };
+
+        let mut backoff = RESTART_BACKOFF_BASE;
+
+        loop {
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+
+            let Some(state) = handle.try_state::<BackendState>() else {
+                break;
+            };
+            if state.is_shutting_down.load(Ordering::SeqCst) {
+                log::info!("Backend supervisor exiting: shutdown in progress");
+                break;
+            }
+
+            if probe_backend_health(&client).await {
+                let was_unhealthy = state.consecutive_failures.swap(0, Ordering::SeqCst) > 0;
+                let became_healthy = !state.is_healthy.swap(true, Ordering::SeqCst);
+                if was_unhealthy || became_healthy {
+                    let _ = handle.emit("backend://healthy", ());
+                }
+                backoff = RESTART_BACKOFF_BASE;
+                continue;
+            }
+
+            state.is_healthy.store(false, Ordering::SeqCst);
+            let failures = state.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
+
+            if failures < MAX_CONSECUTIVE_FAILURES {
+                continue;
+            }
+
+            if state.is_shutting_down.load(Ordering::SeqCst) {
+                break;
+            }
+
+            if state.deliberately_stopped.load(Ordering::SeqCst) {
+                log::info!("Backend supervisor skipping restart: stop was user-initiated");
+                state.consecutive_failures.store(0, Ordering::SeqCst);
+                continue;
+            }
+
+            log::warn!("Backend unresponsive after {} consecutive checks, restarting", failures);
+            let _ = handle.emit("backend://down", ());
+            let _ = handle.emit("backend://restarting", ());
+            let _ = handle.emit("backend-restarting", ());
+            state.consecutive_failures.store(0, Ordering::SeqCst);
+
+            // The process may still be running but just unresponsive - tear
+            // it down first so the relaunch below doesn't fight it for port 9876.
+            teardown_for_restart(&state);
+            state.restart_count.fetch_add(1, Ordering::SeqCst);
+
+            launch_backend(handle.clone()).await;
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(RESTART_BACKOFF_CAP);
+
+            if state.is_healthy.load(Ordering::SeqCst) {
+                state.restart_count.store(0, Ordering::SeqCst);
+            }
+            let _ = handle.emit("backend-restarted", ());
+        }
+    });
 }
 
 /// Show a desktop notification (placeholder - notification plugin removed for now)
@@ -267,6 +1368,60 @@ async fn show_notification(
     Ok(())
 }
 
+/// Return the last `tail` buffered backend log lines, oldest first.
+///
+/// Used to hydrate a log panel on open, or after a backend restart, since
+/// the ring buffer already holds whatever was captured before the panel
+/// subscribed to `backend-log`.
+#[tauri::command]
+fn get_backend_logs(state: tauri::State<'_, BackendState>, tail: usize) -> Vec<LogLine> {
+    let logs = state.logs.lock().unwrap();
+    let start = logs.len().saturating_sub(tail);
+    logs.iter().skip(start).cloned().collect()
+}
+
+/// Check the release manifest for a newer version and, if there is one,
+/// hold it as the pending update and return its version.
+///
+/// Nothing is downloaded or installed here: the held `PendingUpdate` is
+/// handed to `install_update`, which refuses to run while
+/// `TradingState.is_trading` is set.
+#[tauri::command]
+async fn check_for_update() -> Result<Option<String>, String> {
+    let update = updater::check_for_update().await?;
+    match update {
+        Some(update) => {
+            let version = update.version().to_string();
+            let mut guard = PENDING_UPDATE.lock().unwrap();
+            *guard = Some(update);
+            Ok(Some(version))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Download, verify and stage the update found by the last `check_for_update`
+/// call. This only stages the bundle to disk - see `updater::install_update`
+/// for why nothing actually applies it yet.
+#[tauri::command]
+async fn install_update(
+    app: AppHandle,
+    trading_state: tauri::State<'_, TradingState>,
+) -> Result<(), String> {
+    if trading_state.is_trading.load(Ordering::SeqCst) {
+        log::warn!("Update available but trading is active; refusing to install");
+        return Err("Stop all bots before installing an update".to_string());
+    }
+
+    let update = PENDING_UPDATE
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "No update pending - call check_for_update first".to_string())?;
+
+    updater::install_update(app, update).await
+}
+
 /// Create the application menu
 fn create_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<Menu<R>, tauri::Error> {
     let file_menu = Submenu::with_items(
@@ -346,23 +1501,77 @@ fn create_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<Menu<R>, tauri::
     )
 }
 
+/// Show the main window if it's hidden, hide it otherwise.
+fn toggle_main_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    if window.is_visible().unwrap_or(true) {
+        let _ = window.hide();
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Keep the tray's status item and tooltip in sync with `BackendState.is_healthy`.
+///
+/// Polls rather than subscribing to individual events so it picks up every
+/// way the backend can become unhealthy - a failed health check, a sidecar
+/// crash, or either restart path from `spawn_backend_supervisor` - without
+/// needing to listen for each one by name.
+fn spawn_tray_status_updater<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    tray: TrayIcon<R>,
+    status_item: MenuItem<R>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_healthy: Option<bool> = None;
+        loop {
+            let Some(state) = app.try_state::<BackendState>() else {
+                break;
+            };
+            if state.is_shutting_down.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let healthy = state.is_healthy.load(Ordering::SeqCst);
+            if last_healthy != Some(healthy) {
+                let label = if healthy { "Running" } else { "Stopped" };
+                let _ = status_item.set_text(format!("Backend: {}", label));
+                let _ = tray.set_tooltip(Some(&format!("XFactor Bot - Backend {}", label)));
+                last_healthy = Some(healthy);
+            }
+
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+    });
+}
+
 /// Setup the system tray
 fn setup_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), tauri::Error> {
+    let status_item = MenuItem::with_id(app, "tray-status", "Backend: Starting...", false, None::<&str>)?;
+
     let menu = Menu::with_items(
         app,
         &[
-            &MenuItem::with_id(app, "show", "Show XFactor Bot", true, None::<&str>)?,
+            &MenuItem::with_id(app, "show", "Show/Hide Window", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &status_item,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "tray-start-all", "▶ Start All", true, None::<&str>)?,
             &MenuItem::with_id(app, "tray-stop-all", "⏹ Stop All", true, None::<&str>)?,
             &MenuItem::with_id(app, "tray-pause-all", "⏸ Pause All", true, None::<&str>)?,
             &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "tray-kill-switch", "🚨 Kill Switch", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "tray-quit", "Quit", true, None::<&str>)?,
         ],
     )?;
 
-    let _tray = TrayIconBuilder::with_id("main-tray")
+    let tray = TrayIconBuilder::with_id("main-tray")
         .icon(app.default_window_icon().unwrap().clone())
+        .tooltip("XFactor Bot")
         .menu(&menu)
         .show_menu_on_left_click(false)
         .on_tray_icon_event(|tray, event| {
@@ -372,24 +1581,35 @@ fn setup_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), tauri::Error>
                 ..
             } = event
             {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+                toggle_main_window(tray.app_handle());
             }
         })
         .on_menu_event(|app, event| match event.id.as_ref() {
-            "show" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+            "show" => toggle_main_window(app),
+            "tray-start-all" => {
+                tauri::async_runtime::spawn(run_bot_action(app.clone(), "start"));
+            }
+            "tray-stop-all" => {
+                tauri::async_runtime::spawn(run_bot_action(app.clone(), "stop"));
+            }
+            "tray-pause-all" => {
+                tauri::async_runtime::spawn(run_bot_action(app.clone(), "pause"));
+            }
+            "tray-kill-switch" => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let Some(trading_state) = app.try_state::<TradingState>() else {
+                        return;
+                    };
+                    let _ = kill_switch(app.clone(), trading_state).await;
+                });
             }
             "tray-quit" => {
                 log::info!("Tray quit requested, cleaning up...");
-                // Cleanup backend before exiting
+                // Cleanup backend before exiting; the app is going away for
+                // good, so the backend must not be auto-restarted.
                 if let Some(state) = app.try_state::<BackendState>() {
+                    state.is_shutting_down.store(true, Ordering::SeqCst);
                     graceful_kill_backend(&state);
                 }
                 app.exit(0);
@@ -398,11 +1618,22 @@ fn setup_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), tauri::Error>
         })
         .build(app)?;
 
+    spawn_tray_status_updater(app.clone(), tray, status_item);
+
     Ok(())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Install crash reporting before anything else so a panic during setup,
+    // or in the Tauri runtime itself, is still captured.
+    telemetry::init();
+
+    if let Ok(value) = std::env::var("XFACTOR_HIDE_TO_TRAY") {
+        let enabled = !matches!(value.to_ascii_lowercase().as_str(), "0" | "false" | "off");
+        HIDE_TO_TRAY.store(enabled, Ordering::SeqCst);
+    }
+
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_log::Builder::default().build())
         .plugin(tauri_plugin_shell::init())
@@ -435,212 +1666,36 @@ pub fn run() {
 
     builder
         .manage(BackendState::default())
+        .manage(TradingState::default())
+        .manage(BotsState::default())
+        .manage(WindowRoles::default())
         .setup(|app| {
             // Create menu
             let menu = create_menu(app.handle())?;
             app.set_menu(menu)?;
 
+            // Load the declarative bot definitions, if any were left from a previous run
+            if let Ok(path) = bots_config_path(app.handle()) {
+                let bots = load_bots_config(&path);
+                log::info!("Loaded {} bot definition(s) from {}", bots.len(), path.display());
+                *app.state::<BotsState>().bots.lock().unwrap() = bots;
+            }
+
+            // Restore the main window's last-known geometry before it's shown,
+            // so the app doesn't flash at the default centered size first.
+            if let Some(window) = app.get_webview_window("main") {
+                restore_window_geometry(&window);
+                let _ = window.show();
+            }
+
             // Setup system tray
             setup_tray(app.handle())?;
 
-            // Start backend automatically
+            // Start backend automatically, then hand lifecycle ownership to the supervisor
             let handle = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
-                log::info!("Starting backend...");
-                
-                // Give the app a moment to initialize
-                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-                
-                // Collect all possible backend locations
-                let mut candidates: Vec<std::path::PathBuf> = Vec::new();
-                
-                // Binary names to try (in order of preference)
-                let binary_names = [
-                    "xfactor-backend",
-                    "xfactor-backend-x86_64-apple-darwin",
-                    "xfactor-backend-aarch64-apple-darwin",
-                    "xfactor-backend-x86_64-unknown-linux-gnu",
-                    "xfactor-backend-x86_64-pc-windows-msvc.exe",
-                ];
-                
-                // Get various directories to search
-                if let Ok(resource_dir) = handle.path().resource_dir() {
-                    log::info!("Resource dir: {:?}", resource_dir);
-                    
-                    // MacOS folder (where externalBin places binaries in .app bundle)
-                    if let Some(parent) = resource_dir.parent() {
-                        let macos_dir = parent.join("MacOS");
-                        log::info!("MacOS dir: {:?}", macos_dir);
-                        for name in &binary_names {
-                            candidates.push(macos_dir.join(name));
-                        }
-                    }
-                    
-                    // Resources folder itself
-                    for name in &binary_names {
-                        candidates.push(resource_dir.join(name));
-                    }
-                    
-                    // binaries subfolder in Resources
-                    let binaries_dir = resource_dir.join("binaries");
-                    for name in &binary_names {
-                        candidates.push(binaries_dir.join(name));
-                    }
-                }
-                
-                // Also check data folder (for development or manual placement)
-                if let Ok(app_data_dir) = handle.path().app_data_dir() {
-                    log::info!("App data dir: {:?}", app_data_dir);
-                    for name in &binary_names {
-                        candidates.push(app_data_dir.join(name));
-                    }
-                }
-                
-                // Check current executable's directory
-                if let Ok(exe_path) = std::env::current_exe() {
-                    if let Some(exe_dir) = exe_path.parent() {
-                        log::info!("Executable dir: {:?}", exe_dir);
-                        for name in &binary_names {
-                            candidates.push(exe_dir.join(name));
-                        }
-                        // Also check binaries subfolder next to exe
-                        let binaries_dir = exe_dir.join("binaries");
-                        for name in &binary_names {
-                            candidates.push(binaries_dir.join(name));
-                        }
-                    }
-                }
-                
-                // Log all candidates for debugging
-                log::info!("Searching for backend in {} locations...", candidates.len());
-                
-                // Find the first existing backend binary
-                let backend_path = candidates.into_iter().find(|p| {
-                    let exists = p.exists();
-                    if exists {
-                        log::info!("FOUND backend at: {:?}", p);
-                    }
-                    exists
-                });
-                
-                // FIRST: Check if backend is already running
-                // DON'T kill existing backends - just reuse them
-                if is_backend_running() {
-                    log::info!("Backend is already running on port 9876 - reusing existing instance");
-                    // Don't start a new one, just use the existing
-                } else if let Some(backend) = backend_path {
-                    log::info!("No backend running, starting new instance at: {:?}", backend);
-                    
-                    // Start the backend as a detached process (not a child of frontend)
-                    // This prevents the backend from being killed when frontend closes unexpectedly
-                    #[cfg(unix)]
-                    {
-                        // Use setsid on Unix to detach from parent process group
-                        match Command::new("setsid")
-                            .arg(&backend)
-                            .stdout(Stdio::null())
-                            .stderr(Stdio::null())
-                            .spawn()
-                        {
-                            Ok(child) => {
-                                let pid = child.id();
-                                log::info!("Backend started as detached process (PID: {})", pid);
-                                
-                                // Store the PID for cleanup on intentional close
-                                if let Some(state) = handle.try_state::<BackendState>() {
-                                    state.backend_pid.store(pid, Ordering::SeqCst);
-                                }
-                            }
-                            Err(_) => {
-                                // setsid might not be available, try without it
-                                match Command::new(&backend)
-                                    .stdout(Stdio::null())
-                                    .stderr(Stdio::null())
-                                    .spawn()
-                                {
-                                    Ok(child) => {
-                                        let pid = child.id();
-                                        log::info!("Backend started (PID: {})", pid);
-                                        if let Some(state) = handle.try_state::<BackendState>() {
-                                            state.backend_pid.store(pid, Ordering::SeqCst);
-                                        }
-                                    }
-                                    Err(e) => {
-                                        log::error!("Failed to spawn backend: {}", e);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    
-                    #[cfg(windows)]
-                    {
-                        // Windows: Use CREATE_NEW_PROCESS_GROUP to detach
-                        use std::os::windows::process::CommandExt;
-                        const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
-                        const DETACHED_PROCESS: u32 = 0x00000008;
-                        
-                        match Command::new(&backend)
-                            .creation_flags(CREATE_NEW_PROCESS_GROUP | DETACHED_PROCESS)
-                            .stdout(Stdio::null())
-                            .stderr(Stdio::null())
-                            .spawn()
-                        {
-                            Ok(child) => {
-                                let pid = child.id();
-                                log::info!("Backend started as detached process (PID: {})", pid);
-                                if let Some(state) = handle.try_state::<BackendState>() {
-                                    state.backend_pid.store(pid, Ordering::SeqCst);
-                                }
-                            }
-                            Err(e) => {
-                                log::error!("Failed to spawn backend: {}", e);
-                            }
-                        }
-                    }
-                } else {
-                    // Try the sidecar mechanism as fallback (for dev mode)
-                    match handle.shell().sidecar("xfactor-backend") {
-                        Ok(sidecar) => {
-                            match sidecar.spawn() {
-                                Ok((mut rx, child)) => {
-                                    log::info!("Backend sidecar started successfully");
-                                    
-                                    if let Some(state) = handle.try_state::<BackendState>() {
-                                        let mut guard = state.child.lock().unwrap();
-                                        *guard = Some(child);
-                                    }
-                                    
-                                    tauri::async_runtime::spawn(async move {
-                                        use tauri_plugin_shell::process::CommandEvent;
-                                        while let Some(event) = rx.recv().await {
-                                            match event {
-                                                CommandEvent::Stdout(line) => {
-                                                    log::info!("[Backend] {}", String::from_utf8_lossy(&line));
-                                                }
-                                                CommandEvent::Stderr(line) => {
-                                                    log::warn!("[Backend] {}", String::from_utf8_lossy(&line));
-                                                }
-                                                CommandEvent::Terminated(status) => {
-                                                    log::info!("[Backend] Process terminated: {:?}", status);
-                                                    break;
-                                                }
-                                                _ => {}
-                                            }
-                                        }
-                                    });
-                                }
-                                Err(e) => {
-                                    log::error!("Failed to spawn backend sidecar: {}", e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            log::warn!("Backend not found (dev mode?): {}", e);
-                        }
-                    }
-                }
-            });
+            tauri::async_runtime::spawn(launch_backend(handle.clone()));
+            spawn_backend_supervisor(handle.clone());
+            spawn_config_watcher(handle);
 
             // Handle menu events
             app.on_menu_event(|app, event| {
@@ -657,9 +1712,48 @@ pub fn run() {
                         }
                     }
                     "kill-switch" => {
-                        log::warn!("KILL SWITCH activated from menu!");
-                        // Emit event to frontend
-                        let _ = app.emit("kill-switch", ());
+                        let app = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            let Some(trading_state) = app.try_state::<TradingState>() else {
+                                return;
+                            };
+                            let _ = kill_switch(app.clone(), trading_state).await;
+                        });
+                    }
+                    "check-updates" => {
+                        log::info!("Check for updates requested from menu");
+                        let app = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            match check_for_update().await {
+                                Ok(Some(version)) => {
+                                    log::info!("Update {} available, installing", version);
+                                    let Some(trading_state) = app.try_state::<TradingState>() else {
+                                        log::error!("Cannot install update: state not managed yet");
+                                        return;
+                                    };
+                                    if let Err(e) = install_update(app.clone(), trading_state).await {
+                                        log::warn!("Update install failed: {}", e);
+                                    }
+                                }
+                                Ok(None) => log::info!("App is up to date"),
+                                Err(e) => log::warn!("Update check failed: {}", e),
+                            }
+                        });
+                    }
+                    "start-all" => {
+                        tauri::async_runtime::spawn(run_bot_action(app.clone(), "start"));
+                    }
+                    "stop-all" => {
+                        tauri::async_runtime::spawn(run_bot_action(app.clone(), "stop"));
+                    }
+                    "pause-all" => {
+                        tauri::async_runtime::spawn(run_bot_action(app.clone(), "pause"));
+                    }
+                    "import-config" => {
+                        import_bots_config(app);
+                    }
+                    "export-config" => {
+                        export_bots_config(app);
                     }
                     _ => {}
                 }
@@ -670,18 +1764,41 @@ pub fn run() {
         })
         .on_window_event(|window, event| {
             match event {
-                // Stop backend when window closes
-                tauri::WindowEvent::CloseRequested { .. } => {
+                tauri::WindowEvent::CloseRequested { api, .. }
+                    if window.label() == "main" && hide_to_tray_enabled() =>
+                {
+                    log::info!("Window close requested, hiding to tray (backend keeps running)");
+                    save_window_geometry(window);
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+                // Stop backend when the main window closes; this is app
+                // teardown, not a reversible user stop, so mark it shutting
+                // down first. Secondary windows (detached charts, settings,
+                // etc.) close independently and must not touch the backend.
+                tauri::WindowEvent::CloseRequested { .. } if window.label() == "main" => {
                     log::info!("Window close requested, initiating cleanup...");
+                    save_window_geometry(window);
                     if let Some(state) = window.app_handle().try_state::<BackendState>() {
+                        state.is_shutting_down.store(true, Ordering::SeqCst);
                         graceful_kill_backend(&state);
                     }
                 }
+                // Non-main windows: just persist their geometry.
+                tauri::WindowEvent::CloseRequested { .. } => {
+                    save_window_geometry(window);
+                }
                 // Also handle destroy event
                 tauri::WindowEvent::Destroyed => {
-                    log::info!("Window destroyed, final cleanup...");
-                    if let Some(state) = window.app_handle().try_state::<BackendState>() {
-                        graceful_kill_backend(&state);
+                    log::info!("Window '{}' destroyed", window.label());
+                    if let Some(state) = window.app_handle().try_state::<WindowRoles>() {
+                        state.roles.lock().unwrap().remove(window.label());
+                    }
+                    if window.label() == "main" {
+                        if let Some(state) = window.app_handle().try_state::<BackendState>() {
+                            state.is_shutting_down.store(true, Ordering::SeqCst);
+                            graceful_kill_backend(&state);
+                        }
                     }
                 }
                 _ => {}
@@ -694,6 +1811,13 @@ pub fn run() {
             get_system_info,
             show_notification,
             check_backend_health,
+            check_for_update,
+            install_update,
+            get_backend_logs,
+            kill_switch,
+            clear_halt,
+            get_trading_state,
+            register_window_role,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
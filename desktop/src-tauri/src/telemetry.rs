@@ -0,0 +1,104 @@
+//! Crash reporting for the desktop shell and its backend sidecar.
+//!
+//! Covers two failure modes: panics in this process (via a Sentry client +
+//! panic hook) and unexpected exits of the spawned backend, which get
+//! attributed as structured events rather than silently vanishing into the
+//! log. Gated behind the `crash-reporting` build feature; uploads
+//! additionally require runtime opt-in so privacy-sensitive users can
+//! disable them without a rebuild.
+//!
+//! This does not catch crashes that skip unwinding entirely (segfaults,
+//! aborts) - that needs an out-of-process monitor (e.g. `minidumper`'s
+//! client/server split with a real watchdog process) which hasn't been
+//! built yet.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Runtime opt-in switch. Uploads stay off until this is explicitly
+/// enabled, even when the `crash-reporting` feature is compiled in.
+static UPLOADS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable crash report uploads at runtime (e.g. a settings toggle).
+pub fn set_uploads_enabled(enabled: bool) {
+    UPLOADS_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Whether the user has opted in to uploading crash reports.
+pub fn uploads_enabled() -> bool {
+    UPLOADS_ENABLED.load(Ordering::SeqCst)
+}
+
+#[cfg(feature = "crash-reporting")]
+mod backend {
+    use super::uploads_enabled;
+    use std::sync::OnceLock;
+
+    static GUARD: OnceLock<sentry::ClientInitGuard> = OnceLock::new();
+
+    /// Initialize the crash reporter. Must run before the Tauri `Builder`
+    /// is constructed so a panic during setup is still captured.
+    pub fn init() {
+        let Ok(dsn) = std::env::var("XFACTOR_SENTRY_DSN") else {
+            log::info!("XFACTOR_SENTRY_DSN not set, crash reporting disabled");
+            return;
+        };
+
+        let guard = sentry::init((
+            dsn,
+            sentry::ClientOptions {
+                release: Some(env!("CARGO_PKG_VERSION").into()),
+                before_send: Some(std::sync::Arc::new(|event| {
+                    uploads_enabled().then_some(event)
+                })),
+                ..Default::default()
+            },
+        ));
+        let _ = GUARD.set(guard);
+
+        log::info!("Crash reporting initialized (panics only - see module docs)");
+    }
+
+    /// Capture a structured event for an unexpected backend exit.
+    pub fn capture_backend_crash(exit_code: Option<i32>, recent_stderr: &[String]) {
+        sentry::with_scope(
+            |scope| {
+                scope.set_extra("backend_exit_code", exit_code.into());
+                scope.set_extra("backend_stderr_tail", recent_stderr.join("\n").into());
+            },
+            || {
+                sentry::capture_message("Backend sidecar exited unexpectedly", sentry::Level::Error);
+            },
+        );
+    }
+}
+
+/// Initialize crash reporting. Safe to call even when the `crash-reporting`
+/// feature is off, in which case it's a no-op.
+pub fn init() {
+    #[cfg(feature = "crash-reporting")]
+    backend::init();
+
+    #[cfg(not(feature = "crash-reporting"))]
+    log::info!("Crash reporting not compiled in (enable the `crash-reporting` feature)");
+}
+
+/// Attribute an unexpected backend exit to a structured crash event,
+/// subject to the runtime upload opt-in.
+pub fn capture_backend_crash(exit_code: Option<i32>, recent_stderr: &[String]) {
+    if !uploads_enabled() {
+        log::warn!(
+            "Backend exited unexpectedly (code {:?}); not reporting, telemetry opt-in is off",
+            exit_code
+        );
+        return;
+    }
+
+    #[cfg(feature = "crash-reporting")]
+    backend::capture_backend_crash(exit_code, recent_stderr);
+
+    #[cfg(not(feature = "crash-reporting"))]
+    log::warn!(
+        "Backend exited unexpectedly (code {:?}); crash-reporting feature is disabled",
+        exit_code
+    );
+}
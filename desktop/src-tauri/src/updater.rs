@@ -0,0 +1,182 @@
+//! Signed auto-updater.
+//!
+//! Polls a release manifest (version, a platform-keyed download URL, and a
+//! detached minisign/ed25519 signature for each build), compares it against
+//! `CARGO_PKG_VERSION`, downloads the matching bundle, and verifies the
+//! signature before anything is applied - a bad or missing signature aborts
+//! the install rather than silently skipping verification. Downloads go
+//! through a client that honors `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY` so the
+//! update flow still works from behind a corporate proxy.
+
+use std::collections::HashMap;
+
+use futures_util::StreamExt;
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Ed25519 public key (minisign format) this build trusts, embedded at
+/// compile time. Builds signed with any other key are rejected.
+const UPDATE_PUBLIC_KEY: &str =
+    "RWQf6LRCGA9i59aaLeAZQTqz1tN9uWhX0sP1q0V4z5T2q6dA1fM5B4h7";
+
+/// Env var pointing at the release manifest JSON. No built-in default -
+/// an update check is a no-op rather than phoning home to a guessed URL
+/// until this is configured for the build.
+const MANIFEST_URL_ENV: &str = "XFACTOR_UPDATE_MANIFEST_URL";
+
+#[derive(Deserialize)]
+struct ReleaseManifest {
+    version: String,
+    platforms: HashMap<String, PlatformBuild>,
+}
+
+#[derive(Deserialize, Clone)]
+struct PlatformBuild {
+    url: String,
+    /// Detached minisign signature of the bundle at `url`, in minisign's
+    /// text format (the contents of its `.sig` file).
+    signature: String,
+}
+
+/// An update found by `check_for_update`, ready to be handed to `install_update`.
+pub struct PendingUpdate {
+    version: String,
+    build: PlatformBuild,
+}
+
+impl PendingUpdate {
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+/// This build's platform key, matching the keys used in the release manifest.
+fn platform_key() -> String {
+    format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+}
+
+/// Build an HTTP client that honors the standard proxy environment
+/// variables (`HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`/SOCKS) for corporate
+/// networks that require them.
+fn build_http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to build updater HTTP client: {}", e))
+}
+
+/// Compare two dotted numeric version strings (`"1.10.0"` > `"1.9.0"`),
+/// padding the shorter one with zeros rather than comparing lexicographically
+/// - a plain string comparison puts `"1.10.0"` before `"1.9.0"`.
+fn is_newer_version(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version.split('.').map(|p| p.parse().unwrap_or(0)).collect()
+    }
+
+    let (candidate, current) = (parts(candidate), parts(current));
+    let len = candidate.len().max(current.len());
+    for i in 0..len {
+        let c = candidate.get(i).copied().unwrap_or(0);
+        let r = current.get(i).copied().unwrap_or(0);
+        if c != r {
+            return c > r;
+        }
+    }
+    false
+}
+
+/// Poll the release manifest and return an update if the manifest's version
+/// is newer than this build's.
+pub async fn check_for_update() -> Result<Option<PendingUpdate>, String> {
+    let Ok(manifest_url) = std::env::var(MANIFEST_URL_ENV) else {
+        log::info!("{} not set, skipping update check", MANIFEST_URL_ENV);
+        return Ok(None);
+    };
+
+    let client = build_http_client()?;
+    let manifest: ReleaseManifest = client
+        .get(&manifest_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch release manifest: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Malformed release manifest: {}", e))?;
+
+    let current = env!("CARGO_PKG_VERSION");
+    if !is_newer_version(&manifest.version, current) {
+        log::info!("Already up to date (current {}, manifest {})", current, manifest.version);
+        return Ok(None);
+    }
+
+    let key = platform_key();
+    let Some(build) = manifest.platforms.get(&key).cloned() else {
+        return Err(format!("No build published for platform '{}'", key));
+    };
+
+    Ok(Some(PendingUpdate { version: manifest.version, build }))
+}
+
+/// Verify a downloaded bundle against its detached minisign signature.
+fn verify_signature(data: &[u8], signature: &str) -> Result<(), String> {
+    let public_key = minisign_verify::PublicKey::from_base64(UPDATE_PUBLIC_KEY)
+        .map_err(|e| format!("Invalid embedded public key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(signature)
+        .map_err(|e| format!("Malformed update signature: {}", e))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|_| "Update signature verification failed".to_string())
+}
+
+/// Download, verify and stage a pending update - this does NOT install it.
+///
+/// Emits `update-progress` (`{downloaded, total}`) while streaming the
+/// bundle, then `update-ready` with the staged bundle's path once it has
+/// been verified and written to the cache dir. Nothing currently reads that
+/// path back to actually replace the running app or invoke a platform
+/// installer, and the backend is deliberately left running - there would be
+/// nothing to restart it afterwards. Wiring a real apply step (self-replace
+/// + `tauri_plugin_process::restart`, or a platform installer) is tracked
+/// as follow-up work; that step, not this one, is where the backend should
+/// be torn down with `is_shutting_down` set, immediately before relaunch.
+pub async fn install_update(app: AppHandle, update: PendingUpdate) -> Result<(), String> {
+    let client = build_http_client()?;
+    let response = client
+        .get(&update.build.url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download update: {}", e))?;
+
+    let total = response.content_length();
+    let mut downloaded: u64 = 0;
+    let mut bytes: Vec<u8> = Vec::new();
+
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Update download interrupted: {}", e))?;
+        downloaded += chunk.len() as u64;
+        bytes.extend_from_slice(&chunk);
+        let _ = app.emit(
+            "update-progress",
+            serde_json::json!({ "downloaded": downloaded, "total": total }),
+        );
+    }
+
+    verify_signature(&bytes, &update.build.signature)?;
+    log::info!("Update {} downloaded and verified", update.version);
+
+    let staging_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| format!("Failed to resolve cache dir: {}", e))?;
+    std::fs::create_dir_all(&staging_dir).map_err(|e| e.to_string())?;
+    let staged_path = staging_dir.join(format!("xfactor-update-{}", update.version));
+    std::fs::write(&staged_path, &bytes).map_err(|e| format!("Failed to stage update: {}", e))?;
+
+    let _ = app.emit(
+        "update-ready",
+        serde_json::json!({ "version": update.version, "path": staged_path }),
+    );
+
+    Ok(())
+}